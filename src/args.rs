@@ -14,12 +14,42 @@ pub enum Commands {
     Encode {
         file: PathBuf,
         chunk_type: String,
-        message: String,
+        /// Message to hide; omit and use --input-file to hide raw binary data instead
+        message: Option<String>,
+        /// Write the encoded PNG to this file instead of overwriting the input
+        #[arg(long = "output-file", short = 'o')]
         output_file: Option<PathBuf>,
+        /// Encrypt the message with this passphrase before embedding it (falls back to PNGME_PASS)
+        #[arg(long, env = "PNGME_PASS")]
+        passphrase: Option<String>,
+        /// Split the message across multiple chunks carrying at most this many payload bytes each (plus a 12-byte frame header)
+        #[arg(long)]
+        max_chunk_size: Option<usize>,
+        /// Hide the raw bytes of this file instead of a text message
+        #[arg(long, conflicts_with = "message")]
+        input_file: Option<PathBuf>,
+        /// Hide raw bytes given as base64 text instead of a text message
+        #[arg(long, conflicts_with_all = ["message", "input_file"])]
+        input_base64: Option<String>,
+        /// Pack a labeled record into the chunk (repeatable); with this, message/input-file are unused
+        #[arg(long = "record", conflicts_with_all = ["message", "input_file", "input_base64"])]
+        records: Vec<String>,
+        /// Deflate-compress the payload before embedding it, if doing so shrinks it
+        #[arg(long)]
+        compress: bool,
     },
     Decode {
         file: PathBuf,
         chunk_type: String,
+        /// Passphrase to decrypt the message with, if it was encoded with one (falls back to PNGME_PASS)
+        #[arg(long, env = "PNGME_PASS")]
+        passphrase: Option<String>,
+        /// Write the decoded bytes to this file instead of printing them
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Treat the chunk as an RLP-packed list of records and pretty-print each one
+        #[arg(long)]
+        records: bool,
     },
     Remove {
         file: PathBuf,
@@ -45,13 +45,6 @@ impl Chunk {
         &self.data
     }
 
-    pub fn data_as_string(&self) -> crate::Result<String> {
-        match String::from_utf8(self.data.clone()) {
-            Ok(str) => Ok(str),
-            Err(_) => bail!("couldn't convert chunk data to string"),
-        }
-    }
-
     pub fn as_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::with_capacity(self.length as usize + 12);
         bytes.extend_from_slice(&self.length.to_be_bytes());
@@ -61,16 +54,13 @@ impl Chunk {
 
         bytes
     }
-}
 
-impl TryFrom<&[u8]> for Chunk {
-    type Error = Error;
-
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+    /// Reads a single chunk from `reader`, which must be positioned right at its
+    /// length field. Used to parse a PNG incrementally instead of buffering it whole.
+    pub fn from_reader<R: Read>(reader: &mut R) -> crate::Result<Self> {
         let mut len: [u8; 4] = [0, 0, 0, 0];
         let mut chunk_type: [u8; 4] = [0, 0, 0, 0];
         let mut crc: [u8; 4] = [0, 0, 0, 0];
-        let mut reader = BufReader::new(value);
 
         reader.read_exact(&mut len)?;
         let len = u32::from_be_bytes(len);
@@ -78,14 +68,15 @@ impl TryFrom<&[u8]> for Chunk {
         reader.read_exact(&mut chunk_type)?;
         let chunk_type = ChunkType::try_from(chunk_type)?;
 
-        let mut data = Vec::try_from(&value[8..len as usize + 8])?;
-        reader.read_exact(data.as_mut())?;
+        let mut data = vec![0u8; len as usize];
+        reader.read_exact(&mut data)?;
 
         reader.read_exact(&mut crc)?;
         let crc = u32::from_be_bytes(crc);
 
-        let valid_crc = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
-        let valid_crc = valid_crc.checksum(&value[4..len as usize + 8]);
+        let mut crc_input = chunk_type.bytes().to_vec();
+        crc_input.extend_from_slice(&data);
+        let valid_crc = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(&crc_input);
 
         if crc != valid_crc {
             bail!(
@@ -104,6 +95,14 @@ impl TryFrom<&[u8]> for Chunk {
     }
 }
 
+impl TryFrom<&[u8]> for Chunk {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Chunk::from_reader(&mut BufReader::new(value))
+    }
+}
+
 impl Display for Chunk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut output = String::new();
@@ -180,7 +179,7 @@ mod tests {
     #[test]
     fn test_chunk_string() {
         let chunk = testing_chunk();
-        let chunk_string = chunk.data_as_string().unwrap();
+        let chunk_string = String::from_utf8(chunk.data().to_vec()).unwrap();
         let expected_chunk_string = String::from("This is where your secret message will be!");
         assert_eq!(chunk_string, expected_chunk_string);
     }
@@ -209,7 +208,7 @@ mod tests {
 
         let chunk = Chunk::try_from(chunk_data.as_ref()).unwrap();
 
-        let chunk_string = chunk.data_as_string().unwrap();
+        let chunk_string = String::from_utf8(chunk.data().to_vec()).unwrap();
         let expected_chunk_string = String::from("This is where your secret message will be!");
 
         assert_eq!(chunk.length(), 42);
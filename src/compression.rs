@@ -0,0 +1,84 @@
+use std::io::{Read, Write};
+
+use anyhow::bail;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use crate::Result;
+
+const STORED: u8 = 0x00;
+const DEFLATE: u8 = 0x01;
+
+/// Prefixes `data` with a one-byte method tag, deflating it first if `compress`
+/// is requested and doing so actually makes the result smaller.
+pub fn compress(data: &[u8], compress: bool) -> Result<Vec<u8>> {
+    if compress {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(data)?;
+        let deflated = encoder.finish()?;
+
+        if deflated.len() < data.len() {
+            let mut out = Vec::with_capacity(1 + deflated.len());
+            out.push(DEFLATE);
+            out.extend(deflated);
+            return Ok(out);
+        }
+    }
+
+    let mut out = Vec::with_capacity(1 + data.len());
+    out.push(STORED);
+    out.extend_from_slice(data);
+    Ok(out)
+}
+
+/// Strips the method tag added by [`compress`], inflating the payload if needed.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    let (&method, payload) = data
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("empty chunk data"))?;
+
+    match method {
+        STORED => Ok(payload.to_vec()),
+        DEFLATE => {
+            let mut inflated = Vec::new();
+            ZlibDecoder::new(payload).read_to_end(&mut inflated)?;
+            Ok(inflated)
+        }
+        other => bail!("unknown compression method tag: {}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_stored() {
+        let data = b"This is where your secret message will be!";
+        let compressed = compress(data, false).unwrap();
+        assert_eq!(compressed[0], STORED);
+        assert_eq!(decompress(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_deflate() {
+        let data = vec![b'a'; 1000];
+        let compressed = compress(&data, true).unwrap();
+        assert_eq!(compressed[0], DEFLATE);
+        assert_eq!(decompress(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_compress_keeps_stored_when_deflate_would_not_shrink() {
+        let data: Vec<u8> = (0..=255).collect();
+        let compressed = compress(&data, true).unwrap();
+        assert_eq!(compressed[0], STORED);
+        assert_eq!(decompress(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decompress_rejects_unknown_method_tag() {
+        assert!(decompress(&[0xff, 1, 2, 3]).is_err());
+    }
+}
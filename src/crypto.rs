@@ -0,0 +1,101 @@
+use anyhow::bail;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use pbkdf2::pbkdf2_hmac;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::Sha256;
+
+use crate::Result;
+
+const MAGIC: [u8; 4] = *b"PME1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + NONCE_LEN;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ITERATIONS, &mut key);
+    key
+}
+
+/// Returns true if `data` looks like it was produced by [`encrypt`].
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.len() >= HEADER_LEN && data[..MAGIC.len()] == MAGIC
+}
+
+/// Encrypts `plaintext` with a key derived from `passphrase`, returning
+/// `magic || salt || nonce || ciphertext+tag`.
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = Nonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|_| anyhow::anyhow!("failed to encrypt message"))?;
+
+    let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypts data laid out as `magic || salt || nonce || ciphertext+tag`,
+/// failing if the passphrase is wrong or the data has been tampered with.
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if !is_encrypted(data) {
+        bail!("data does not look like a pngme-encrypted payload");
+    }
+
+    let salt = &data[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce_bytes = &data[MAGIC.len() + SALT_LEN..HEADER_LEN];
+    let ciphertext = &data[HEADER_LEN..];
+
+    let key = derive_key(passphrase, salt);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| anyhow::anyhow!("wrong passphrase or tampered data: authentication failed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let plaintext = b"This is where your secret message will be!";
+        let encrypted = encrypt(plaintext, "correct horse battery staple").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert_eq!(
+            decrypt(&encrypted, "correct horse battery staple").unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_passphrase() {
+        let encrypted = encrypt(b"hello", "right passphrase").unwrap();
+        assert!(decrypt(&encrypted, "wrong passphrase").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampered_ciphertext() {
+        let mut encrypted = encrypt(b"hello", "passphrase").unwrap();
+        let last = encrypted.len() - 1;
+        encrypted[last] ^= 0xff;
+        assert!(decrypt(&encrypted, "passphrase").is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::bail;
+
+use crate::Result;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const PAD: u8 = b'=';
+
+/// Encodes `data` as standard (RFC 4648) base64 with `=` padding.
+pub fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[((b0 & 0b11) << 4 | b1 >> 4) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[((b1 & 0b1111) << 2 | b2 >> 6) as usize] as char
+        } else {
+            PAD as char
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[(b2 & 0b0011_1111) as usize] as char
+        } else {
+            PAD as char
+        });
+    }
+
+    out
+}
+
+fn decode_char(c: u8) -> Result<u8> {
+    match c {
+        b'A'..=b'Z' => Ok(c - b'A'),
+        b'a'..=b'z' => Ok(c - b'a' + 26),
+        b'0'..=b'9' => Ok(c - b'0' + 52),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => bail!("invalid base64 character: {}", c as char),
+    }
+}
+
+/// Decodes standard (RFC 4648) base64 text, with or without `=` padding.
+pub fn decode(data: &str) -> Result<Vec<u8>> {
+    let data = data.trim().trim_end_matches('=');
+    let chars: Vec<u8> = data.bytes().collect();
+
+    if chars.len() % 4 == 1 {
+        bail!("invalid base64 length: {}", chars.len());
+    }
+
+    let mut out = Vec::with_capacity(chars.len() / 4 * 3);
+    for group in chars.chunks(4) {
+        let mut sextets = [0u8; 4];
+        for (i, &c) in group.iter().enumerate() {
+            sextets[i] = decode_char(c)?;
+        }
+
+        out.push(sextets[0] << 2 | sextets[1] >> 4);
+        if group.len() > 2 {
+            out.push(sextets[1] << 4 | sextets[2] >> 2);
+        }
+        if group.len() > 3 {
+            out.push(sextets[2] << 6 | sextets[3]);
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_matches_known_vectors() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_decode_matches_known_vectors() {
+        assert_eq!(decode("").unwrap(), b"");
+        assert_eq!(decode("Zg==").unwrap(), b"f");
+        assert_eq!(decode("Zm8=").unwrap(), b"fo");
+        assert_eq!(decode("Zm9v").unwrap(), b"foo");
+        assert_eq!(decode("Zm9vYmFy").unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn test_roundtrip_arbitrary_bytes() {
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode(&encode(&data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_character() {
+        assert!(decode("not valid base64!").is_err());
+    }
+}
@@ -1,5 +1,6 @@
-use std::{fs, str::FromStr};
+use std::{ffi::OsString, fs, io::BufWriter, str::FromStr};
 
+use anyhow::bail;
 use args::{Commands, PngMe};
 use chunk::Chunk;
 use chunk_type::ChunkType;
@@ -9,8 +10,12 @@ use png::Png;
 mod args;
 mod chunk;
 mod chunk_type;
-mod commands;
+mod compression;
+mod crypto;
+mod encoding;
 mod png;
+mod rlp;
+mod spanning;
 
 pub type Error = anyhow::Error;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -24,39 +29,138 @@ fn main() -> Result<()> {
             chunk_type,
             message,
             output_file,
+            passphrase,
+            max_chunk_size,
+            input_file,
+            input_base64,
+            records,
+            compress,
         } => {
             let chunk_type = ChunkType::from_str(chunk_type)?;
             let bytes = fs::read(file)?;
 
             let mut png = Png::try_from(bytes.as_ref())?;
-            let chunk = Chunk::new(chunk_type, message.clone().into_bytes());
-            png.append_chunk(chunk);
+            let payload = if !records.is_empty() {
+                rlp::encode(
+                    &records
+                        .iter()
+                        .map(|record| record.clone().into_bytes())
+                        .collect::<Vec<_>>(),
+                )
+            } else {
+                match (message, input_file, input_base64) {
+                    (Some(message), None, None) => message.clone().into_bytes(),
+                    (None, Some(input_file), None) => fs::read(input_file)?,
+                    (None, None, Some(input_base64)) => encoding::decode(input_base64)?,
+                    (None, None, None) => {
+                        bail!("pass a message, --input-file, --input-base64, or --record")
+                    }
+                    _ => bail!("pass only one of a message, --input-file, or --input-base64"),
+                }
+            };
+            let payload = compression::compress(&payload, *compress)?;
+            let data = match passphrase {
+                Some(passphrase) => crypto::encrypt(&payload, passphrase)?,
+                None => payload,
+            };
+
+            match max_chunk_size {
+                Some(max_chunk_size) if data.len() > *max_chunk_size => {
+                    for frame in spanning::split(&data, *max_chunk_size)? {
+                        png.append_chunk(Chunk::new(
+                            ChunkType::try_from(chunk_type.bytes())?,
+                            frame,
+                        ));
+                    }
+                }
+                _ => png.append_chunk(Chunk::new(chunk_type, data)),
+            }
+
             if let Some(op_file) = output_file {
                 fs::write(op_file, png.as_bytes())?;
             } else {
                 fs::write(file, png.as_bytes())?;
             }
         }
-        Commands::Decode { file, chunk_type } => {
-            let bytes = fs::read(file)?;
-            let png = Png::try_from(bytes.as_ref())?;
+        Commands::Decode {
+            file,
+            chunk_type,
+            passphrase,
+            output,
+            records,
+        } => {
+            let png = Png::from_reader(fs::File::open(file)?)?;
 
-            if let Some(chunk) = png.chunk_by_type(chunk_type) {
-                println!("Decoded Message: {}", chunk.data_as_string()?);
+            let chunks = png.chunks_by_type(chunk_type);
+            let data = match chunks.as_slice() {
+                [] => None,
+                [chunk, ..] if !spanning::is_spanned(chunk.data()) => Some(chunk.data().to_vec()),
+                chunks => {
+                    let frames: Vec<&[u8]> = chunks.iter().map(|chunk| chunk.data()).collect();
+                    Some(spanning::reassemble(&frames)?)
+                }
+            };
+
+            if let Some(data) = data {
+                let data = if crypto::is_encrypted(&data) {
+                    let passphrase = passphrase.as_ref().ok_or_else(|| {
+                        anyhow::anyhow!("this message is encrypted; pass --passphrase")
+                    })?;
+                    crypto::decrypt(&data, passphrase)?
+                } else {
+                    data
+                };
+                let data = compression::decompress(&data)?;
+
+                if let Some(output) = output {
+                    fs::write(output, &data)?;
+                } else if *records {
+                    for (i, record) in rlp::decode(&data)?.iter().enumerate() {
+                        match String::from_utf8(record.clone()) {
+                            Ok(text) => println!("Record {}: {}", i, text),
+                            Err(_) => {
+                                println!("Record {} (base64): {}", i, encoding::encode(record))
+                            }
+                        }
+                    }
+                } else {
+                    match String::from_utf8(data) {
+                        Ok(message) => println!("Decoded Message: {}", message),
+                        Err(err) => {
+                            println!(
+                                "Decoded Message (base64): {}",
+                                encoding::encode(&err.into_bytes())
+                            )
+                        }
+                    }
+                }
             }
         }
         Commands::Remove { file, chunk_type } => {
-            let bytes = fs::read(file)?;
-            let mut png = Png::try_from(bytes.as_ref())?;
+            let mut tmp_name = file.as_os_str().to_owned();
+            tmp_name.push(OsString::from(".pngme-tmp"));
+            let tmp_path = std::path::PathBuf::from(tmp_name);
 
-            png.remove_first_chunk(chunk_type)?;
-            fs::write(file, png.as_bytes())?;
+            let result = Png::stream_remove(
+                fs::File::open(file)?,
+                &mut BufWriter::new(fs::File::create(&tmp_path)?),
+                chunk_type,
+            );
+            match result {
+                Ok(()) => fs::rename(&tmp_path, file)?,
+                Err(err) => {
+                    fs::remove_file(&tmp_path)?;
+                    return Err(err);
+                }
+            }
         }
         Commands::Print { file } => {
-            let bytes = fs::read(file)?;
-            let png = Png::try_from(bytes.as_ref())?;
-
-            println!("{}", png);
+            println!("PNG {{");
+            Png::for_each_chunk(fs::File::open(file)?, |chunk| {
+                println!("  {},", chunk);
+                Ok(())
+            })?;
+            println!("}}");
         }
     }
 
@@ -0,0 +1,119 @@
+use std::io::{BufRead, BufReader, Read, Write};
+
+use anyhow::bail;
+
+use crate::chunk::Chunk;
+use crate::Error;
+
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    /// Collects every chunk of the given type, in the order they appear in the PNG.
+    pub fn chunks_by_type(&self, chunk_type: &str) -> Vec<&Chunk> {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.chunk_type().to_string() == chunk_type)
+            .collect()
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes)
+            .expect("writing to a Vec<u8> cannot fail");
+        bytes
+    }
+
+    /// Writes the PNG header followed by every chunk, one at a time, so callers
+    /// can stream the output without building the whole file in memory first.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_all(&Self::STANDARD_HEADER)?;
+        for chunk in &self.chunks {
+            writer.write_all(&chunk.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Parses a PNG incrementally, reading the signature and then one chunk at a
+    /// time, instead of requiring the whole file to already be in memory.
+    pub fn from_reader<R: Read>(reader: R) -> crate::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        read_header(&mut reader)?;
+
+        let mut chunks = Vec::new();
+        while !reader.fill_buf()?.is_empty() {
+            chunks.push(Chunk::from_reader(&mut reader)?);
+        }
+
+        Ok(Png { chunks })
+    }
+
+    /// Reads `reader` one chunk at a time, calling `visit` on each without ever
+    /// holding more than a single chunk in memory.
+    pub fn for_each_chunk<R: Read>(
+        reader: R,
+        mut visit: impl FnMut(&Chunk) -> crate::Result<()>,
+    ) -> crate::Result<()> {
+        let mut reader = BufReader::new(reader);
+        read_header(&mut reader)?;
+
+        while !reader.fill_buf()?.is_empty() {
+            visit(&Chunk::from_reader(&mut reader)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies `reader` to `writer` one chunk at a time, dropping the first chunk
+    /// of `chunk_type` and writing every other chunk straight through. Returns
+    /// an error if no chunk of that type was found.
+    pub fn stream_remove<R: Read, W: Write>(
+        reader: R,
+        writer: &mut W,
+        chunk_type: &str,
+    ) -> crate::Result<()> {
+        let mut reader = BufReader::new(reader);
+        read_header(&mut reader)?;
+        writer.write_all(&Self::STANDARD_HEADER)?;
+
+        let mut removed = false;
+        while !reader.fill_buf()?.is_empty() {
+            let chunk = Chunk::from_reader(&mut reader)?;
+            if !removed && chunk.chunk_type().to_string() == chunk_type {
+                removed = true;
+                continue;
+            }
+            writer.write_all(&chunk.as_bytes())?;
+        }
+
+        if !removed {
+            bail!("no chunk of type {} found", chunk_type);
+        }
+
+        Ok(())
+    }
+}
+
+fn read_header<R: Read>(reader: &mut R) -> crate::Result<()> {
+    let mut header = [0u8; Png::STANDARD_HEADER.len()];
+    reader.read_exact(&mut header)?;
+    if header != Png::STANDARD_HEADER {
+        bail!("input does not start with the PNG header");
+    }
+    Ok(())
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Png::from_reader(value)
+    }
+}
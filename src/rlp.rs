@@ -0,0 +1,197 @@
+use anyhow::bail;
+
+use crate::Result;
+
+enum Item<'a> {
+    Str(&'a [u8]),
+    List(&'a [u8]),
+}
+
+fn minimal_be_bytes(mut n: u64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    while n > 0 {
+        bytes.push((n & 0xff) as u8);
+        n >>= 8;
+    }
+    bytes.reverse();
+    bytes
+}
+
+fn encode_length_prefixed(short_base: u8, long_base: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 9);
+    if payload.len() <= 55 {
+        out.push(short_base + payload.len() as u8);
+    } else {
+        let len_bytes = minimal_be_bytes(payload.len() as u64);
+        out.push(long_base + len_bytes.len() as u8);
+        out.extend_from_slice(&len_bytes);
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+fn encode_item(item: &[u8]) -> Vec<u8> {
+    if item.len() == 1 && item[0] <= 0x7f {
+        return item.to_vec();
+    }
+    encode_length_prefixed(0x80, 0xb7, item)
+}
+
+/// Encodes `records` as a single RLP list of byte strings.
+pub fn encode(records: &[Vec<u8>]) -> Vec<u8> {
+    let body: Vec<u8> = records
+        .iter()
+        .flat_map(|record| encode_item(record))
+        .collect();
+    encode_length_prefixed(0xc0, 0xf7, &body)
+}
+
+fn be_bytes_to_usize(bytes: &[u8]) -> Result<usize> {
+    if bytes.len() > 8 {
+        bail!("RLP length is too large");
+    }
+    let mut n: u64 = 0;
+    for &b in bytes {
+        n = (n << 8) | b as u64;
+    }
+    usize::try_from(n).map_err(|_| anyhow::anyhow!("RLP length is too large"))
+}
+
+fn parse_item(data: &[u8]) -> Result<(Item<'_>, usize)> {
+    let prefix = *data
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("unexpected end of RLP data"))?;
+
+    match prefix {
+        0x00..=0x7f => Ok((Item::Str(&data[..1]), 1)),
+        0x80..=0xb7 => {
+            let len = (prefix - 0x80) as usize;
+            if data.len() < 1 + len {
+                bail!("truncated RLP string");
+            }
+            let payload = &data[1..1 + len];
+            if len == 1 && payload[0] <= 0x7f {
+                bail!("non-canonical RLP encoding: single byte must encode itself");
+            }
+            Ok((Item::Str(payload), 1 + len))
+        }
+        0xb8..=0xbf => {
+            let len_of_len = (prefix - 0xb7) as usize;
+            if data.len() < 1 + len_of_len {
+                bail!("truncated RLP length");
+            }
+            let len_bytes = &data[1..1 + len_of_len];
+            if len_bytes[0] == 0 {
+                bail!("non-canonical RLP length encoding");
+            }
+            let len = be_bytes_to_usize(len_bytes)?;
+            if len <= 55 {
+                bail!("non-canonical RLP encoding: length fits in the short form");
+            }
+            let start = 1 + len_of_len;
+            if data.len() < start + len {
+                bail!("truncated RLP string");
+            }
+            Ok((Item::Str(&data[start..start + len]), start + len))
+        }
+        0xc0..=0xf7 => {
+            let len = (prefix - 0xc0) as usize;
+            if data.len() < 1 + len {
+                bail!("truncated RLP list");
+            }
+            Ok((Item::List(&data[1..1 + len]), 1 + len))
+        }
+        0xf8..=0xff => {
+            let len_of_len = (prefix - 0xf7) as usize;
+            if data.len() < 1 + len_of_len {
+                bail!("truncated RLP length");
+            }
+            let len_bytes = &data[1..1 + len_of_len];
+            if len_bytes[0] == 0 {
+                bail!("non-canonical RLP length encoding");
+            }
+            let len = be_bytes_to_usize(len_bytes)?;
+            if len <= 55 {
+                bail!("non-canonical RLP encoding: length fits in the short form");
+            }
+            let start = 1 + len_of_len;
+            if data.len() < start + len {
+                bail!("truncated RLP list");
+            }
+            Ok((Item::List(&data[start..start + len]), start + len))
+        }
+    }
+}
+
+/// Decodes a single top-level RLP list of byte strings, rejecting trailing
+/// bytes, truncated lengths, non-canonical encodings, and nested lists.
+pub fn decode(data: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let (item, consumed) = parse_item(data)?;
+    if consumed != data.len() {
+        bail!("trailing bytes after RLP list");
+    }
+
+    let list_body = match item {
+        Item::List(body) => body,
+        Item::Str(_) => bail!("expected an RLP list at the top level"),
+    };
+
+    let mut records = Vec::new();
+    let mut rest = list_body;
+    while !rest.is_empty() {
+        let (item, consumed) = parse_item(rest)?;
+        match item {
+            Item::Str(bytes) => records.push(bytes.to_vec()),
+            Item::List(_) => bail!("nested RLP lists are not supported"),
+        }
+        rest = &rest[consumed..];
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_empty_list() {
+        let records: Vec<Vec<u8>> = vec![];
+        assert_eq!(decode(&encode(&records)).unwrap(), records);
+    }
+
+    #[test]
+    fn test_roundtrip_short_strings() {
+        let records = vec![b"author".to_vec(), b"hello".to_vec()];
+        assert_eq!(decode(&encode(&records)).unwrap(), records);
+    }
+
+    #[test]
+    fn test_roundtrip_single_byte() {
+        let records = vec![vec![0x00], vec![0x7f], vec![0x80]];
+        assert_eq!(decode(&encode(&records)).unwrap(), records);
+    }
+
+    #[test]
+    fn test_roundtrip_long_string_and_list() {
+        let records = vec![vec![b'a'; 100], vec![b'b'; 4]];
+        assert_eq!(decode(&encode(&records)).unwrap(), records);
+    }
+
+    #[test]
+    fn test_decode_rejects_trailing_bytes() {
+        let mut bytes = encode(&[b"hi".to_vec()]);
+        bytes.push(0x00);
+        assert!(decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_non_canonical_single_byte_string() {
+        assert!(decode(&[0xc2, 0x81, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_length() {
+        assert!(decode(&[0xc1, 0xb8]).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::bail;
+
+use crate::Result;
+
+const MAGIC: [u8; 4] = *b"PMS1";
+const HEADER_LEN: usize = MAGIC.len() + 2 + 2 + 4;
+
+/// Returns true if `data` starts with a spanning frame header produced by [`split`].
+pub fn is_spanned(data: &[u8]) -> bool {
+    data.len() >= HEADER_LEN && data[..MAGIC.len()] == MAGIC
+}
+
+/// Splits `data` into frames of at most `max_data_size` payload bytes each, every
+/// frame prefixed with `magic(4) || total_parts(u16) || part_index(u16) || total_len(u32)`.
+pub fn split(data: &[u8], max_data_size: usize) -> Result<Vec<Vec<u8>>> {
+    if max_data_size == 0 {
+        bail!("max chunk data size must be greater than zero");
+    }
+
+    let total_len = data.len() as u32;
+    let parts: Vec<&[u8]> = data.chunks(max_data_size).collect();
+    if parts.len() > u16::MAX as usize {
+        bail!(
+            "message splits into {} parts, which exceeds the {} part limit; pass a larger --max-chunk-size",
+            parts.len(),
+            u16::MAX
+        );
+    }
+    let total_parts = parts.len() as u16;
+
+    Ok(parts
+        .iter()
+        .enumerate()
+        .map(|(part_index, payload)| {
+            let mut frame = Vec::with_capacity(HEADER_LEN + payload.len());
+            frame.extend_from_slice(&MAGIC);
+            frame.extend_from_slice(&total_parts.to_be_bytes());
+            frame.extend_from_slice(&(part_index as u16).to_be_bytes());
+            frame.extend_from_slice(&total_len.to_be_bytes());
+            frame.extend_from_slice(payload);
+            frame
+        })
+        .collect())
+}
+
+struct Frame<'a> {
+    total_parts: u16,
+    part_index: u16,
+    total_len: u32,
+    payload: &'a [u8],
+}
+
+fn parse_frame(data: &[u8]) -> Result<Frame<'_>> {
+    if !is_spanned(data) {
+        bail!("data does not look like a spanning frame");
+    }
+
+    let total_parts = u16::from_be_bytes([data[4], data[5]]);
+    let part_index = u16::from_be_bytes([data[6], data[7]]);
+    let total_len = u32::from_be_bytes([data[8], data[9], data[10], data[11]]);
+
+    Ok(Frame {
+        total_parts,
+        part_index,
+        total_len,
+        payload: &data[HEADER_LEN..],
+    })
+}
+
+/// Reassembles the frames produced by [`split`] back into the original payload,
+/// erroring if any part is missing, duplicated, or disagrees with the others.
+pub fn reassemble(frame_data: &[&[u8]]) -> Result<Vec<u8>> {
+    if frame_data.is_empty() {
+        bail!("no spanning frames to reassemble");
+    }
+
+    let mut frames: Vec<Frame<'_>> = frame_data
+        .iter()
+        .map(|data| parse_frame(data))
+        .collect::<Result<_>>()?;
+    frames.sort_by_key(|frame| frame.part_index);
+
+    let total_parts = frames[0].total_parts;
+    let total_len = frames[0].total_len;
+
+    if frames.len() != total_parts as usize {
+        bail!("expected {} parts but found {}", total_parts, frames.len());
+    }
+
+    for (expected_index, frame) in frames.iter().enumerate() {
+        if frame.total_parts != total_parts || frame.total_len != total_len {
+            bail!("spanning frames disagree on total_parts/total_len");
+        }
+        if frame.part_index != expected_index as u16 {
+            bail!(
+                "missing or duplicated part: expected index {} but found {}",
+                expected_index,
+                frame.part_index
+            );
+        }
+    }
+
+    let mut data = Vec::with_capacity(total_len as usize);
+    for frame in &frames {
+        data.extend_from_slice(frame.payload);
+    }
+
+    if data.len() != total_len as usize {
+        bail!(
+            "reassembled {} bytes but expected {}",
+            data.len(),
+            total_len
+        );
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let frames = split(&data, 64).unwrap();
+        assert!(frames.len() > 1);
+
+        let frame_refs: Vec<&[u8]> = frames.iter().map(|frame| frame.as_slice()).collect();
+        assert_eq!(reassemble(&frame_refs).unwrap(), data);
+    }
+
+    #[test]
+    fn test_roundtrip_single_frame() {
+        let data = b"short message".to_vec();
+        let frames = split(&data, 1024).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(!is_spanned(&data));
+        assert!(is_spanned(&frames[0]));
+
+        let frame_refs: Vec<&[u8]> = frames.iter().map(|frame| frame.as_slice()).collect();
+        assert_eq!(reassemble(&frame_refs).unwrap(), data);
+    }
+
+    #[test]
+    fn test_reassemble_rejects_missing_part() {
+        let data: Vec<u8> = (0..100).collect();
+        let frames = split(&data, 10).unwrap();
+
+        let frame_refs: Vec<&[u8]> = frames[..frames.len() - 1]
+            .iter()
+            .map(|frame| frame.as_slice())
+            .collect();
+        assert!(reassemble(&frame_refs).is_err());
+    }
+
+    #[test]
+    fn test_reassemble_rejects_duplicated_part_index() {
+        let data: Vec<u8> = (0..100).collect();
+        let frames = split(&data, 10).unwrap();
+
+        let mut frame_refs: Vec<&[u8]> = frames[..frames.len() - 1]
+            .iter()
+            .map(|frame| frame.as_slice())
+            .collect();
+        frame_refs.push(&frames[0]);
+        assert!(reassemble(&frame_refs).is_err());
+    }
+
+    #[test]
+    fn test_reassemble_rejects_disagreeing_frames() {
+        let a = split(&[0u8; 20], 10).unwrap();
+        let b = split(&[0u8; 30], 10).unwrap();
+
+        let frame_refs: Vec<&[u8]> = vec![&a[0], &b[1]];
+        assert!(reassemble(&frame_refs).is_err());
+    }
+
+    #[test]
+    fn test_split_rejects_too_many_parts() {
+        let huge = vec![0u8; u16::MAX as usize + 2];
+        assert!(split(&huge, 1).is_err());
+    }
+}